@@ -23,13 +23,50 @@ pub use self::windows_input::SyncReader;
 use self::windows_input::WindowsInput;
 
 pub use self::input::{input, TerminalInput};
+use bitflags::bitflags;
 use crossterm_utils::Result;
+use std::collections::VecDeque;
 use std::io;
+use std::time::Duration;
 use std::sync::{
     mpsc::{Receiver, Sender},
     Arc,
 };
 
+/// A FIFO buffer of decoded `InputEvent`s shared by the platform readers.
+///
+/// The backends drain their raw source (the TTY file descriptor on UNIX, the
+/// console input queue on Windows) into this buffer, which lets `poll` report
+/// readiness and `read` hand out a single event at a time without re-parsing.
+#[derive(Debug, Default)]
+pub struct EventBuffer {
+    events: VecDeque<InputEvent>,
+}
+
+impl EventBuffer {
+    /// Creates an empty buffer.
+    pub fn new() -> EventBuffer {
+        EventBuffer {
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Appends a freshly decoded event to the back of the buffer.
+    pub fn push(&mut self, event: InputEvent) {
+        self.events.push_back(event);
+    }
+
+    /// Returns whether at least one event is buffered and ready to be read.
+    pub fn is_ready(&self) -> bool {
+        !self.events.is_empty()
+    }
+
+    /// Removes and returns the next buffered event, if any.
+    pub fn next(&mut self) -> Option<InputEvent> {
+        self.events.pop_front()
+    }
+}
+
 /// This trait defines the actions that can be performed with the terminal input.
 /// This trait can be implemented so that a concrete implementation of the ITerminalInput can fulfill
 /// the wishes to work on a specific platform.
@@ -49,8 +86,48 @@ trait ITerminalInput {
     fn read_sync(&self) -> SyncReader;
     fn enable_mouse_mode(&self) -> Result<()>;
     fn disable_mouse_mode(&self) -> Result<()>;
+    /// Enable bracketed paste mode so pasted text arrives as a single
+    /// `InputEvent::Paste` instead of a stream of `KeyCode::Char` events.
+    ///
+    /// The default is a no-op for platforms that do not support it.
+    fn enable_bracketed_paste(&self) -> Result<()> {
+        Ok(())
+    }
+    /// Disable bracketed paste mode.
+    ///
+    /// The default is a no-op for platforms that do not support it.
+    fn disable_bracketed_paste(&self) -> Result<()> {
+        Ok(())
+    }
+    /// Wait until an `InputEvent` is available or `timeout` elapses, returning
+    /// whether an event is ready to be read. A `None` timeout blocks until an
+    /// event arrives. Events are kept in an internal buffer so a successful
+    /// poll is followed by a non-blocking `read`.
+    ///
+    /// The default reports that no event is ready; platforms that maintain an
+    /// [`EventBuffer`] override it.
+    fn poll(&self, _timeout: Option<Duration>) -> Result<bool> {
+        Ok(false)
+    }
+    /// Read and consume the next buffered `InputEvent`, blocking until one is
+    /// available.
+    ///
+    /// The default yields [`InputEvent::Unknown`]; platforms that maintain an
+    /// [`EventBuffer`] override it.
+    fn read(&self) -> Result<InputEvent> {
+        Ok(InputEvent::Unknown)
+    }
 }
 
+/// The DEC private sequence that turns bracketed paste mode on.
+pub const ENABLE_BRACKETED_PASTE: &str = "\x1B[?2004h";
+/// The DEC private sequence that turns bracketed paste mode off.
+pub const DISABLE_BRACKETED_PASTE: &str = "\x1B[?2004l";
+/// The CSI marker the terminal emits just before pasted text.
+pub const PASTE_BEGIN: &[u8] = b"\x1B[200~";
+/// The CSI marker the terminal emits just after pasted text.
+pub const PASTE_END: &[u8] = b"\x1B[201~";
+
 /// Enum to specify which input event has occurred.
 #[derive(Debug, PartialOrd, PartialEq, Hash, Clone)]
 pub enum InputEvent {
@@ -58,6 +135,16 @@ pub enum InputEvent {
     Keyboard(KeyEvent),
     /// A mouse event occurred.
     Mouse(MouseEvent),
+    /// The terminal window was resized, carrying the new column and row count.
+    ///
+    /// It is produced by the platform reader loops: the UNIX `SIGWINCH` handler
+    /// pushes it through the reader channel after querying the new size with
+    /// `ioctl(TIOCGWINSZ)`, and the Windows reader emits it when it dequeues a
+    /// `WINDOW_BUFFER_SIZE_EVENT` record from the console input queue.
+    Resize(u16, u16),
+    /// Text was pasted while bracketed paste mode was enabled, delivered as a
+    /// single atomic event rather than a storm of individual key presses.
+    Paste(String),
     /// A unsupported event has occurred.
     Unsupported(Vec<u8>),
     /// An unknown event has occurred.
@@ -67,16 +154,68 @@ pub enum InputEvent {
 /// Enum to specify which mouse event has occurred.
 #[derive(Debug, PartialOrd, PartialEq, Hash, Clone, Copy)]
 pub enum MouseEvent {
-    /// A mouse press has occurred, this contains the pressed button and the position of the press.
-    Press(MouseButton, u16, u16),
-    /// A mouse button was released.
-    Release(u16, u16),
-    /// A mouse button was hold.
-    Hold(u16, u16),
+    /// A mouse press has occurred, this contains the pressed button, the
+    /// position of the press and the modifier keys held at the time.
+    Press(MouseButton, u16, u16, KeyModifiers),
+    /// A mouse button was released, with its position and held modifiers.
+    Release(u16, u16, KeyModifiers),
+    /// A mouse button was held down, with its position and held modifiers.
+    Hold(u16, u16, KeyModifiers),
+    /// The cursor was moved with a button held down (drag), carrying the
+    /// dragged button, the position and the held modifiers.
+    Drag(MouseButton, u16, u16, KeyModifiers),
     /// An unknown mouse event has occurred.
     Unknown,
 }
 
+impl MouseEvent {
+    /// Decodes an SGR (1006) mouse report into a `MouseEvent`.
+    ///
+    /// `cb` is the button/flags byte, `column` and `row` are 1-based
+    /// coordinates and `released` is `true` for the final `m` (release) form
+    /// and `false` for `M` (press). Bits 2/3/4 of `cb` carry Shift/Alt/Ctrl,
+    /// bit 5 marks motion and bit 6 marks a wheel event.
+    pub fn from_sgr(cb: u8, column: u16, row: u16, released: bool) -> MouseEvent {
+        let mut modifiers = KeyModifiers::empty();
+        if cb & 0b0000_0100 != 0 {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+        if cb & 0b0000_1000 != 0 {
+            modifiers |= KeyModifiers::ALT;
+        }
+        if cb & 0b0001_0000 != 0 {
+            modifiers |= KeyModifiers::CONTROL;
+        }
+
+        let motion = cb & 0b0010_0000 != 0;
+
+        if cb & 0b0100_0000 != 0 {
+            let button = match cb & 0b0000_0011 {
+                0 => MouseButton::WheelUp,
+                1 => MouseButton::WheelDown,
+                2 => MouseButton::WheelLeft,
+                _ => MouseButton::WheelRight,
+            };
+            return MouseEvent::Press(button, column, row, modifiers);
+        }
+
+        let button = match cb & 0b0000_0011 {
+            0 => MouseButton::Left,
+            1 => MouseButton::Middle,
+            2 => MouseButton::Right,
+            _ => return MouseEvent::Release(column, row, modifiers),
+        };
+
+        if motion {
+            MouseEvent::Drag(button, column, row, modifiers)
+        } else if released {
+            MouseEvent::Release(column, row, modifiers)
+        } else {
+            MouseEvent::Press(button, column, row, modifiers)
+        }
+    }
+}
+
 /// Enum to define mouse buttons.
 #[derive(Debug, PartialOrd, PartialEq, Hash, Clone, Copy)]
 pub enum MouseButton {
@@ -90,11 +229,48 @@ pub enum MouseButton {
     WheelUp,
     /// Scroll down
     WheelDown,
+    /// Scroll left (horizontal wheel / trackpad)
+    WheelLeft,
+    /// Scroll right (horizontal wheel / trackpad)
+    WheelRight,
 }
 
-/// Enum with different key or key combinations.
-#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Hash)]
-pub enum KeyEvent {
+/// Represents a key event, a key code together with the modifier keys that
+/// were held down while it was pressed.
+///
+/// The modifiers allow any combination (e.g. Ctrl+Shift+Up, Ctrl+Alt+Char)
+/// to be expressed uniformly instead of enumerating every variant.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct KeyEvent {
+    /// The key that was pressed.
+    pub code: KeyCode,
+    /// The modifier keys that were held down.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyEvent {
+    /// Creates a new key event from a key code and its active modifiers.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent { code, modifiers }
+    }
+}
+
+impl From<KeyCode> for KeyEvent {
+    fn from(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+}
+
+/// Enum with different keys that can be pressed.
+///
+/// The modifier state (Shift/Control/Alt) is carried separately by
+/// [`KeyEvent::modifiers`], so a plain key and its modified forms share a
+/// single variant here.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum KeyCode {
     Backspace,
     Left,
     Right,
@@ -109,16 +285,74 @@ pub enum KeyEvent {
     Insert,
     F(u8),
     Char(char),
-    Alt(char),
-    Ctrl(char),
     Null,
     Esc,
-    CtrlUp,
-    CtrlDown,
-    CtrlRight,
-    CtrlLeft,
-    ShiftUp,
-    ShiftDown,
-    ShiftRight,
-    ShiftLeft,
+}
+
+bitflags! {
+    /// The modifier keys that were held down during a key or mouse event.
+    #[derive(Default)]
+    pub struct KeyModifiers: u8 {
+        const SHIFT = 0b0000_0001;
+        const CONTROL = 0b0000_0010;
+        const ALT = 0b0000_0100;
+    }
+}
+
+impl KeyModifiers {
+    /// Decodes the modifier mask of a CSI `1;<mod>` sequence.
+    ///
+    /// The wire value is `1 + bit_sum`, where the bits encode `Shift = 1`,
+    /// `Alt = 2` and `Ctrl = 4`. These bits are mapped explicitly rather than
+    /// reinterpreted as the in-memory flag layout, so the two orderings stay
+    /// independent.
+    pub fn from_csi_modifier(parameter: u8) -> KeyModifiers {
+        let bits = parameter.saturating_sub(1);
+        let mut modifiers = KeyModifiers::empty();
+        if bits & 0b0000_0001 != 0 {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+        if bits & 0b0000_0010 != 0 {
+            modifiers |= KeyModifiers::ALT;
+        }
+        if bits & 0b0000_0100 != 0 {
+            modifiers |= KeyModifiers::CONTROL;
+        }
+        modifiers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyModifiers, MouseButton, MouseEvent};
+
+    #[test]
+    fn from_csi_modifier_maps_wire_bits() {
+        assert_eq!(KeyModifiers::from_csi_modifier(1), KeyModifiers::empty());
+        assert_eq!(KeyModifiers::from_csi_modifier(2), KeyModifiers::SHIFT);
+        assert_eq!(KeyModifiers::from_csi_modifier(3), KeyModifiers::ALT);
+        assert_eq!(KeyModifiers::from_csi_modifier(5), KeyModifiers::CONTROL);
+        assert_eq!(
+            KeyModifiers::from_csi_modifier(6),
+            KeyModifiers::SHIFT | KeyModifiers::CONTROL
+        );
+    }
+
+    #[test]
+    fn from_sgr_decodes_buttons_motion_and_modifiers() {
+        assert_eq!(
+            MouseEvent::from_sgr(0, 1, 1, false),
+            MouseEvent::Press(MouseButton::Left, 1, 1, KeyModifiers::empty())
+        );
+        // Left button with motion -> drag, Ctrl held (bit 4).
+        assert_eq!(
+            MouseEvent::from_sgr(0b0011_0000, 3, 4, false),
+            MouseEvent::Drag(MouseButton::Left, 3, 4, KeyModifiers::CONTROL)
+        );
+        // Wheel bit (64) + code 3 -> horizontal right scroll.
+        assert_eq!(
+            MouseEvent::from_sgr(0b0100_0011, 5, 6, false),
+            MouseEvent::Press(MouseButton::WheelRight, 5, 6, KeyModifiers::empty())
+        );
+    }
 }